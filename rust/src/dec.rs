@@ -0,0 +1,667 @@
+//! Decoder: parse TOON text back into a `serde_json::Value`.
+//!
+//! This mirrors the encoder in `lib.rs`: where `to_toon` walks a `Value` and
+//! emits indentation-driven text, `from_toon` tokenizes that text line by
+//! line, groups lines into blocks by indentation, and rebuilds the `Value`
+//! recursively (classic tokenize-then-build parser).
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Error returned when TOON text cannot be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    Message(String),
+}
+
+impl ParseError {
+    fn new(msg: impl Into<String>) -> Self {
+        ParseError::Message(msg.into())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Message(msg) => write!(f, "TOON parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a TOON string back into a `serde_json::Value`.
+///
+/// This is the inverse of [`to_toon`](crate::to_toon): it understands plain
+/// `key: value` entries, nested blocks (`key:` followed by a deeper-indented
+/// child block), dash-prefixed lists (`- item`), and the tabular
+/// `key[N]{f1,f2,f3}:` header used for arrays of objects (including the
+/// compact inline form `[N]{fields}:row1;row2`).
+///
+/// # Examples
+///
+/// ```
+/// use totoon::{to_toon, from_toon};
+/// use serde_json::json;
+///
+/// let data = json!({"name": "Alice", "age": 30});
+/// let toon = to_toon(&data);
+/// assert_eq!(from_toon(&toon).unwrap(), data);
+/// ```
+pub fn from_toon(input: &str) -> Result<Value, ParseError> {
+    let lines = tokenize(input);
+    parse_block(&lines)
+}
+
+/// Parse a TOON string and re-serialize it as a JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use totoon::toon_to_json;
+///
+/// let toon = "name: Alice\nage: 30";
+/// let json_str = toon_to_json(toon).unwrap();
+/// assert!(json_str.contains("\"name\":\"Alice\""));
+/// ```
+pub fn toon_to_json(input: &str) -> Result<String, ParseError> {
+    let value = from_toon(input)?;
+    serde_json::to_string(&value).map_err(|e| ParseError::new(e.to_string()))
+}
+
+struct Line {
+    indent: usize,
+    content: String,
+}
+
+fn tokenize(input: &str) -> Vec<Line> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let indent = line.len() - line.trim_start().len();
+            Line {
+                indent,
+                content: line.trim_start().trim_end().to_string(),
+            }
+        })
+        .collect()
+}
+
+struct TabularHeader {
+    key: String,
+    count: usize,
+    fields: Vec<String>,
+    inline: Option<String>,
+}
+
+/// Try to parse a line's content as a tabular header: `key[N]{f1,f2}:` with
+/// an optional inline row payload (`[N]{f1,f2}:row1;row2`) and an optional
+/// (possibly empty) key.
+fn parse_tabular_header(content: &str) -> Option<TabularHeader> {
+    let open_brace = content.find('{')?;
+    let close_brace_rel = content[open_brace..].find('}')?;
+    let close_brace = open_brace + close_brace_rel;
+
+    let before = &content[..open_brace];
+    let open_bracket = before.find('[')?;
+    let close_bracket = before.find(']')?;
+    if close_bracket < open_bracket {
+        return None;
+    }
+
+    let key = before[..open_bracket].to_string();
+    let count: usize = before[open_bracket + 1..close_bracket].parse().ok()?;
+    let fields_str = &content[open_brace + 1..close_brace];
+    let fields: Vec<String> = if fields_str.is_empty() {
+        Vec::new()
+    } else {
+        fields_str.split(',').map(|s| s.to_string()).collect()
+    };
+
+    let after = &content[close_brace + 1..];
+    let rest = after.strip_prefix(':')?;
+    let inline = if rest.is_empty() {
+        None
+    } else {
+        Some(rest.to_string())
+    };
+
+    Some(TabularHeader {
+        key,
+        count,
+        fields,
+        inline,
+    })
+}
+
+/// Parse a slice of lines that all share the same base indentation into a
+/// `Value`. Groups each top-level line with the deeper-indented lines that
+/// follow it (its children), then decides whether the block as a whole is
+/// an object, a list, or (at the root) a bare tabular array.
+fn parse_block(lines: &[Line]) -> Result<Value, ParseError> {
+    if lines.is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    if lines.len() == 1 {
+        match lines[0].content.as_str() {
+            "{}" => return Ok(Value::Object(Map::new())),
+            "[]" => return Ok(Value::Array(Vec::new())),
+            _ => {}
+        }
+    }
+
+    let base = lines[0].indent;
+    let mut groups: Vec<(&Line, &[Line])> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].indent != base {
+            return Err(ParseError::new(format!(
+                "unexpected indentation on line '{}'",
+                lines[i].content
+            )));
+        }
+        let mut j = i + 1;
+        while j < lines.len() && lines[j].indent > base {
+            j += 1;
+        }
+        groups.push((&lines[i], &lines[i + 1..j]));
+        i = j;
+    }
+
+    // A single bare tabular header (no key) is a standalone array, not an object.
+    if groups.len() == 1 {
+        if let Some(header) = parse_tabular_header(&groups[0].0.content) {
+            if header.key.is_empty() {
+                return parse_tabular_value(&header, groups[0].1);
+            }
+        }
+    }
+
+    if groups[0].0.content == "-" || groups[0].0.content.starts_with("- ") {
+        let mut arr = Vec::with_capacity(groups.len());
+        for (header, children) in &groups {
+            if !(header.content == "-" || header.content.starts_with("- ")) {
+                return Err(ParseError::new(format!(
+                    "expected list item, found '{}'",
+                    header.content
+                )));
+            }
+            let rest = if header.content == "-" {
+                ""
+            } else {
+                header.content[2..].trim()
+            };
+            if rest.is_empty() {
+                arr.push(parse_block(children)?);
+            } else {
+                arr.push(parse_field_value(rest)?);
+            }
+        }
+        return Ok(Value::Array(arr));
+    }
+
+    // A single line with no nested children and no `key:` separator is a
+    // bare scalar (or bracket/brace literal) at the root of the document.
+    if groups.len() == 1 && groups[0].1.is_empty() && !groups[0].0.content.contains(':') {
+        return parse_field_value(&groups[0].0.content);
+    }
+
+    let mut map = Map::new();
+    let mut idx = 0;
+    while idx < groups.len() {
+        let (header, children) = groups[idx];
+        if let Some(tab_header) = parse_tabular_header(&header.content) {
+            let value = parse_tabular_value(&tab_header, children)?;
+            map.insert(tab_header.key.clone(), value);
+            idx += 1;
+            continue;
+        }
+        let pos = header.content.find(':').ok_or_else(|| {
+            ParseError::new(format!("expected 'key: value', found '{}'", header.content))
+        })?;
+        let key = header.content[..pos].to_string();
+        let rest = header.content[pos + 1..].trim();
+        if !rest.is_empty() {
+            map.insert(key, parse_field_value(rest)?);
+            idx += 1;
+            continue;
+        }
+        if !children.is_empty() {
+            map.insert(key, parse_block(children)?);
+            idx += 1;
+            continue;
+        }
+        // `key:` with nothing indented under it is ambiguous: it's either
+        // an empty scalar string, or (because the encoder renders an empty
+        // nested object/array as a bare `{}`/`[]` literal on its own,
+        // un-indented line) the start of an empty child value that landed
+        // as the next sibling group instead of a real child.
+        if let Some((next_header, next_children)) = groups.get(idx + 1) {
+            if next_children.is_empty()
+                && (next_header.content == "{}" || next_header.content == "[]")
+            {
+                let value = if next_header.content == "{}" {
+                    Value::Object(Map::new())
+                } else {
+                    Value::Array(Vec::new())
+                };
+                map.insert(key, value);
+                idx += 2;
+                continue;
+            }
+        }
+        map.insert(key, Value::String(String::new()));
+        idx += 1;
+    }
+    Ok(Value::Object(map))
+}
+
+fn parse_tabular_value(header: &TabularHeader, children: &[Line]) -> Result<Value, ParseError> {
+    let row_strs: Vec<String> = if let Some(inline) = &header.inline {
+        split_top_level(inline, ';', Some(header.count))?
+    } else {
+        if children.len() != header.count {
+            return Err(ParseError::new(format!(
+                "tabular header declared {} rows but found {}",
+                header.count,
+                children.len()
+            )));
+        }
+        children.iter().map(|l| l.content.clone()).collect()
+    };
+
+    let mut arr = Vec::with_capacity(header.count);
+    for row in row_strs {
+        let values = split_top_level(&row, ',', Some(header.fields.len()))?;
+        let mut obj = Map::new();
+        for (key, raw) in header.fields.iter().zip(values.iter()) {
+            obj.insert(key.clone(), parse_field_value(raw)?);
+        }
+        arr.push(Value::Object(obj));
+    }
+    Ok(Value::Array(arr))
+}
+
+/// Parse a single value token that appeared inline (a tabular data cell, a
+/// bracketed array, a braced compact object, a quoted string, or a bare
+/// scalar).
+fn parse_field_value(raw: &str) -> Result<Value, ParseError> {
+    let v = raw.trim();
+    if v.is_empty() {
+        return Ok(Value::String(String::new()));
+    }
+    if let Some(header) = parse_tabular_header(v) {
+        return parse_tabular_value(&header, &[]);
+    }
+    if v == "[]" {
+        return Ok(Value::Array(Vec::new()));
+    }
+    if v == "{}" {
+        return Ok(Value::Object(Map::new()));
+    }
+    if let Some(inner) = v.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        let items = split_top_level(inner, ',', None)?;
+        let mut arr = Vec::with_capacity(items.len());
+        for item in items {
+            arr.push(parse_scalar(&item));
+        }
+        return Ok(Value::Array(arr));
+    }
+    if let Some(inner) = v.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        let pairs = split_top_level(inner, ',', None)?;
+        let mut obj = Map::new();
+        for pair in pairs {
+            let pos = pair
+                .find(':')
+                .ok_or_else(|| ParseError::new(format!("expected 'key:value', found '{}'", pair)))?;
+            let key = pair[..pos].to_string();
+            obj.insert(key, parse_field_value(&pair[pos + 1..])?);
+        }
+        return Ok(Value::Object(obj));
+    }
+    Ok(parse_scalar(v))
+}
+
+fn parse_scalar(raw: &str) -> Value {
+    let s = raw.trim();
+    match s {
+        "null" => return Value::Null,
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        return Value::String(unescape_string(&s[1..s.len() - 1]));
+    }
+    if let Ok(i) = s.parse::<i64>() {
+        return Value::Number(i.into());
+    }
+    if let Ok(f) = s.parse::<f64>() {
+        if let Some(n) = serde_json::Number::from_f64(f) {
+            return Value::Number(n);
+        }
+    }
+    Value::String(s.to_string())
+}
+
+fn unescape_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Split `s` on top-level occurrences of `delim`, honoring quoted strings
+/// and bracket/brace nesting (including the compact inline tabular form,
+/// which is not fully bracket-delimited on its own). If `expected` is
+/// `Some(n)`, the result must contain exactly `n` tokens.
+fn split_top_level(s: &str, delim: char, expected: Option<usize>) -> Result<Vec<String>, ParseError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    if chars.is_empty() {
+        if let Some(0) = expected {
+            return Ok(tokens);
+        }
+    }
+    while pos < chars.len() {
+        let end = consume_token(&chars, pos, delim, None)?;
+        tokens.push(chars[pos..end].iter().collect());
+        pos = end;
+        if pos < chars.len() {
+            if chars[pos] != delim {
+                return Err(ParseError::new(format!(
+                    "expected '{}' delimiter in '{}'",
+                    delim, s
+                )));
+            }
+            pos += 1;
+            // A trailing delimiter leaves one more (empty) token after it.
+            // Only honor this for fixed-arity splits (tabular rows/headers):
+            // generic bracket/brace literals tolerate (and drop) a trailing
+            // delimiter instead, as they always have.
+            if pos == chars.len() && expected.is_some() {
+                tokens.push(String::new());
+            }
+        }
+    }
+    if let Some(n) = expected {
+        if tokens.len() != n {
+            return Err(ParseError::new(format!(
+                "expected {} fields, found {} in '{}'",
+                n,
+                tokens.len(),
+                s
+            )));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Consume one token starting at `pos` and return the index just past it.
+/// Handles quoted strings, bracketed arrays (including nested inline
+/// tabular headers, which read their own row payload), and braced objects;
+/// anything else is read up to the next occurrence of `delim` or,
+/// if given, `extra_stop` (the row separator, when scanning a field inside
+/// an inline tabular row, so a single-field row doesn't swallow the `;`
+/// that ends it).
+fn consume_token(
+    chars: &[char],
+    pos: usize,
+    delim: char,
+    extra_stop: Option<char>,
+) -> Result<usize, ParseError> {
+    if pos >= chars.len() {
+        return Ok(pos);
+    }
+    match chars[pos] {
+        '"' => {
+            let mut i = pos + 1;
+            while i < chars.len() {
+                if chars[i] == '\\' {
+                    i += 2;
+                    continue;
+                }
+                if chars[i] == '"' {
+                    return Ok(i + 1);
+                }
+                i += 1;
+            }
+            Err(ParseError::new("unterminated quoted string"))
+        }
+        '[' => {
+            let rest: String = chars[pos..].iter().collect();
+            if let Some(header) = parse_tabular_header(&rest) {
+                if header.key.is_empty() {
+                    return consume_inline_tabular(chars, pos, &header);
+                }
+            }
+            let mut depth = 0usize;
+            let mut i = pos;
+            while i < chars.len() {
+                match chars[i] {
+                    '[' => depth += 1,
+                    ']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Err(ParseError::new("unterminated '['"))
+        }
+        '{' => {
+            let mut depth = 0usize;
+            let mut i = pos;
+            while i < chars.len() {
+                match chars[i] {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Err(ParseError::new("unterminated '{'"))
+        }
+        _ => {
+            let mut i = pos;
+            while i < chars.len() && chars[i] != delim && Some(chars[i]) != extra_stop {
+                i += 1;
+            }
+            Ok(i)
+        }
+    }
+}
+
+/// Consume an entire inline tabular token (`[N]{fields}:row1;row2`) starting
+/// at `pos`, including its row payload, and return the index just past it.
+fn consume_inline_tabular(
+    chars: &[char],
+    pos: usize,
+    header: &TabularHeader,
+) -> Result<usize, ParseError> {
+    let colon_offset = chars[pos..]
+        .iter()
+        .position(|&c| c == ':')
+        .ok_or_else(|| ParseError::new("malformed inline tabular header"))?;
+    let mut i = pos + colon_offset + 1; // past the ':'
+    if header.count == 0 {
+        return Ok(i);
+    }
+    for row_idx in 0..header.count {
+        for field_idx in 0..header.fields.len() {
+            let end = consume_token(chars, i, ',', Some(';'))?;
+            i = end;
+            let is_last_field = field_idx + 1 == header.fields.len();
+            if !is_last_field {
+                if i >= chars.len() || chars[i] != ',' {
+                    return Err(ParseError::new("malformed inline tabular row"));
+                }
+                i += 1;
+            }
+        }
+        let is_last_row = row_idx + 1 == header.count;
+        if !is_last_row {
+            if i >= chars.len() || chars[i] != ';' {
+                return Err(ParseError::new("malformed inline tabular rows"));
+            }
+            i += 1;
+        }
+    }
+    Ok(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_toon;
+    use serde_json::json;
+
+    #[test]
+    fn test_roundtrip_simple_object() {
+        let data = json!({"name": "Alice", "age": 30});
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_nested_object() {
+        let data = json!({
+            "user": {
+                "name": "Alice",
+                "details": {"age": 30, "city": "NYC"}
+            }
+        });
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_list_of_objects() {
+        let data = json!([
+            {"age": 30, "name": "Alice"},
+            {"age": 25, "name": "Bob"}
+        ]);
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_simple_list() {
+        let data = json!([1, 2, 3]);
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_primitives() {
+        for data in [json!(null), json!(true), json!(false), json!(42), json!("hello")] {
+            let toon = to_toon(&data);
+            assert_eq!(from_toon(&toon).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_string_escaping() {
+        let data = json!({"message": "Hello\nWorld\t\"quoted\""});
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_complex_structure() {
+        let data = json!({
+            "metadata": {"count": 2, "timestamp": "2024-01-01"},
+            "users": [
+                {"active": true, "age": 30, "name": "Alice"},
+                {"active": false, "age": 25, "name": "Bob"}
+            ]
+        });
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_nested_tabular_array() {
+        let data = json!({
+            "wrap": {
+                "users": [
+                    {"a": 1, "b": 2},
+                    {"a": 3, "b": 4}
+                ]
+            }
+        });
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_field_holding_nested_inline_tabular_array() {
+        let data = json!({
+            "people": [
+                {"name": "Al", "tags": [{"id": 1}, {"id": 2}]},
+                {"name": "Bo", "tags": [{"id": 3, "v": "a"}, {"id": 4, "v": "b"}]}
+            ]
+        });
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_row_with_trailing_empty_field() {
+        // A row with a missing trailing field encodes as an empty cell, and
+        // decodes back as an empty string (there's no way to distinguish
+        // "missing" from "empty" in a fixed-width tabular row).
+        let data = json!([{"a": 1}, {"a": 2, "b": 3}]);
+        let toon = to_toon(&data);
+        let expected = json!([{"a": 1, "b": ""}, {"a": 2, "b": 3}]);
+        assert_eq!(from_toon(&toon).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_string_and_empty_nested_object() {
+        let data = json!({"a": "", "b": {}, "c": []});
+        let toon = to_toon(&data);
+        assert_eq!(from_toon(&toon).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_object_and_array() {
+        assert_eq!(from_toon(&to_toon(&json!({}))).unwrap(), json!({}));
+        assert_eq!(from_toon(&to_toon(&json!([]))).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn test_toon_to_json() {
+        let toon = "name: Alice\nage: 30";
+        let result = toon_to_json(toon).unwrap();
+        assert!(result.contains("\"name\":\"Alice\""));
+        assert!(result.contains("\"age\":30"));
+    }
+}
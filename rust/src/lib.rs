@@ -4,7 +4,35 @@
 //! when interfacing with Large Language Models (LLMs).
 
 use serde_json::Value;
-use std::collections::HashMap;
+
+mod dec;
+mod options;
+mod select;
+mod ser;
+
+pub use dec::{from_toon, toon_to_json, ParseError};
+pub use options::{Delimiter, QuoteStrategy, ToonEncoder, ToonOptions};
+pub use select::to_toon_selective;
+pub use ser::{to_toon_string, Error};
+
+/// Controls how object/tabular keys are ordered in the encoded output.
+///
+/// `AsEncountered` (the default) walks keys in the order they were first
+/// seen - the author's intended column order - rather than silently
+/// reordering them. `Sorted` forces alphabetical order, matching this
+/// crate's original behavior.
+///
+/// Note: for plain (non-tabular) objects, "as encountered" can only
+/// reflect the source's true field order if the `Value` itself preserved
+/// it, i.e. `serde_json` was built with its `preserve_order` feature;
+/// otherwise `serde_json::Map` is backed by a `BTreeMap` and already
+/// iterates alphabetically by the time it reaches this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyOrder {
+    #[default]
+    AsEncountered,
+    Sorted,
+}
 
 /// Convert a serde_json::Value to TOON format string
 ///
@@ -26,13 +54,47 @@ pub fn to_toon(value: &Value) -> String {
     to_toon_with_indent(value, 2, 0)
 }
 
+/// Convert a serde_json::Value to TOON, choosing how keys are ordered.
+///
+/// # Examples
+///
+/// ```
+/// use totoon::{to_toon_with_key_order, KeyOrder};
+/// use serde_json::json;
+///
+/// let data = json!({"users": [{"name": "Alice", "age": 30}]});
+/// let toon = to_toon_with_key_order(&data, KeyOrder::Sorted);
+/// assert!(toon.contains("[1]{age,name}:"));
+/// ```
+pub fn to_toon_with_key_order(value: &Value, order: KeyOrder) -> String {
+    let opts = ToonOptions {
+        key_order: order,
+        ..ToonOptions::default()
+    };
+    render(value, 0, &opts)
+}
+
 /// Convert a serde_json::Value to TOON format with custom indentation
 pub fn to_toon_with_indent(value: &Value, indent: usize, level: usize) -> String {
+    let opts = ToonOptions {
+        indent_width: indent,
+        ..ToonOptions::default()
+    };
+    render(value, level, &opts)
+}
+
+/// Render `value` to TOON using the full set of [`ToonOptions`]; the
+/// entry point behind [`ToonEncoder::encode`].
+pub(crate) fn render_with_options(value: &Value, level: usize, opts: &ToonOptions) -> String {
+    render(value, level, opts)
+}
+
+fn render(value: &Value, level: usize, opts: &ToonOptions) -> String {
     match value {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Number(n) => n.to_string(),
-        Value::String(s) => escape_string(s),
+        Value::String(s) => escape_string_with_options(s, opts),
         Value::Array(arr) => {
             if arr.is_empty() {
                 return "[]".to_string();
@@ -40,12 +102,12 @@ pub fn to_toon_with_indent(value: &Value, indent: usize, level: usize) -> String
             // Check if it's a list of objects (tabular format)
             if let Some(Value::Object(_)) = arr.first() {
                 if arr.iter().all(|v| v.is_object()) {
-                    return list_of_objects_to_toon("", arr, indent, level);
+                    return list_of_objects_to_toon("", arr, level, opts);
                 }
             }
-            list_to_toon(arr, indent, level)
+            list_to_toon(arr, level, opts)
         }
-        Value::Object(obj) => dict_to_toon(obj, indent, level),
+        Value::Object(obj) => dict_to_toon(obj, level, opts),
     }
 }
 
@@ -65,33 +127,42 @@ pub fn json_to_toon(json_str: &str) -> Result<String, serde_json::Error> {
     Ok(to_toon(&value))
 }
 
-fn dict_to_toon(obj: &serde_json::Map<String, Value>, indent: usize, level: usize) -> String {
+fn dict_to_toon(obj: &serde_json::Map<String, Value>, level: usize, opts: &ToonOptions) -> String {
     if obj.is_empty() {
         return "{}".to_string();
     }
 
     let mut lines = Vec::new();
-    let prefix = " ".repeat(indent * level);
+    let prefix = opts.prefix(level);
 
-    for (key, value) in obj {
+    let entries: Vec<(&String, &Value)> = match opts.key_order {
+        KeyOrder::Sorted => {
+            let mut entries: Vec<(&String, &Value)> = obj.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            entries
+        }
+        KeyOrder::AsEncountered => obj.iter().collect(),
+    };
+
+    for (key, value) in entries {
         match value {
             Value::Object(inner_obj) if !inner_obj.is_empty() => {
                 lines.push(format!("{}{}:", prefix, key));
-                lines.push(dict_to_toon(inner_obj, indent, level + 1));
+                lines.push(dict_to_toon(inner_obj, level + 1, opts));
             }
             Value::Array(arr) if !arr.is_empty() => {
                 // Check if it's a list of objects
                 if let Some(Value::Object(_)) = arr.first() {
                     if arr.iter().all(|v| v.is_object()) {
-                        lines.push(list_of_objects_to_toon(key, arr, indent, level));
+                        lines.push(list_of_objects_to_toon(key, arr, level, opts));
                         continue;
                     }
                 }
                 lines.push(format!("{}{}:", prefix, key));
-                lines.push(list_to_toon(arr, indent, level + 1));
+                lines.push(list_to_toon(arr, level + 1, opts));
             }
             _ => {
-                let value_str = value_to_toon(value, indent, level + 1);
+                let value_str = value_to_toon(value, level + 1, opts);
                 lines.push(format!("{}{}: {}", prefix, key, value_str));
             }
         }
@@ -100,65 +171,71 @@ fn dict_to_toon(obj: &serde_json::Map<String, Value>, indent: usize, level: usiz
     lines.join("\n")
 }
 
-fn list_to_toon(arr: &[Value], indent: usize, level: usize) -> String {
+fn list_to_toon(arr: &[Value], level: usize, opts: &ToonOptions) -> String {
     if arr.is_empty() {
         return "[]".to_string();
     }
 
     let mut lines = Vec::new();
-    let prefix = " ".repeat(indent * level);
+    let prefix = opts.prefix(level);
 
     for item in arr {
-        let value_str = value_to_toon(item, indent, level);
+        let value_str = value_to_toon(item, level, opts);
         lines.push(format!("{}- {}", prefix, value_str));
     }
 
     lines.join("\n")
 }
 
-fn list_of_objects_to_toon(
-    key: &str,
-    arr: &[Value],
-    indent: usize,
-    level: usize,
-) -> String {
+/// Gather the union of keys across `keys`, deduplicated in first-seen
+/// order; sorted afterward when `order` is `KeyOrder::Sorted`.
+fn collect_keys<'a, I: IntoIterator<Item = &'a String>>(keys: I, order: KeyOrder) -> Vec<String> {
+    let mut all_keys: Vec<String> = Vec::new();
+    for k in keys {
+        if !all_keys.contains(k) {
+            all_keys.push(k.clone());
+        }
+    }
+    if order == KeyOrder::Sorted {
+        all_keys.sort();
+    }
+    all_keys
+}
+
+fn list_of_objects_to_toon(key: &str, arr: &[Value], level: usize, opts: &ToonOptions) -> String {
     if arr.is_empty() {
         return "[]".to_string();
     }
 
     let mut lines = Vec::new();
-    let prefix = " ".repeat(indent * level);
-
-    // Collect all unique keys from all objects
-    let mut seen_keys = HashMap::new();
-
-    for item in arr {
-        if let Value::Object(obj) = item {
-            for k in obj.keys() {
-                seen_keys.insert(k.clone(), true);
-            }
-        }
-    }
-
-    if seen_keys.is_empty() {
+    let prefix = opts.prefix(level);
+    let delim = opts.delimiter.as_char();
+
+    let all_keys = collect_keys(
+        arr.iter().filter_map(|item| match item {
+            Value::Object(obj) => Some(obj.keys()),
+            _ => None,
+        }).flatten(),
+        opts.key_order,
+    );
+
+    if all_keys.is_empty() {
         return "[]".to_string();
     }
 
-    // Sort keys for consistent output (HashMap doesn't preserve order)
-    let mut all_keys: Vec<String> = seen_keys.keys().cloned().collect();
-    all_keys.sort();
-
     // Header format: key[count]{field1,field2,field3}:
     let count = arr.len();
-    let fields = all_keys.join(",");
+    let fields = all_keys.join(&delim.to_string());
     if !key.is_empty() {
         lines.push(format!("{}{}[{}]{{{}}}:", prefix, key, count, fields));
     } else {
         lines.push(format!("{}[{}]{{{}}}:", prefix, count, fields));
     }
 
-    // Data rows: comma-separated values with 2 spaces indentation
-    let data_prefix = "  "; // Two spaces for data rows
+    // Data rows sit one level deeper than the header, honoring the active
+    // indentation settings so the header stays distinguishable from its
+    // rows at every nesting depth (not just at the root).
+    let data_prefix = opts.prefix(level + 1);
     let empty_value = Value::String(String::new());
     for item in arr {
         if let Value::Object(obj) = item {
@@ -171,76 +248,57 @@ fn list_of_objects_to_toon(
                             "[]".to_string()
                         } else if let Some(Value::Object(_)) = arr_val.first() {
                             // Array of objects: use compact inline tabular format
-                            let mut nested_keys_map = HashMap::new();
-                            for nested_item in arr_val {
-                                if let Value::Object(nested_obj) = nested_item {
-                                    for nk in nested_obj.keys() {
-                                        nested_keys_map.insert(nk.clone(), true);
-                                    }
-                                }
-                            }
-                            let mut nested_keys: Vec<String> = nested_keys_map.keys().cloned().collect();
-                            nested_keys.sort();
-                            let nested_fields = nested_keys.join(",");
+                            let nested_keys = collect_keys(
+                                arr_val.iter().filter_map(|item| match item {
+                                    Value::Object(obj) => Some(obj.keys()),
+                                    _ => None,
+                                }).flatten(),
+                                opts.key_order,
+                            );
+                            let nested_fields = nested_keys.join(&delim.to_string());
                             let nested_count = arr_val.len();
-                            
-                            // Build compact data rows separated by semicolons
+
+                            // Build compact data rows separated by the row separator
                             let mut nested_rows = Vec::new();
                             for nested_item in arr_val {
                                 if let Value::Object(nested_obj) = nested_item {
                                     let mut nested_row_values = Vec::new();
                                     for nk in &nested_keys {
                                         let nv = nested_obj.get(nk).unwrap_or(&empty_value);
-                                        let mut nv_str = value_to_toon(nv, 0, 0);
-                                        if nv_str.contains(',') || nv_str.contains(';') || nv_str.contains(':') {
-                                            nv_str = format!("\"{}\"", nv_str);
-                                        }
+                                        let nv_str = row_cell_to_toon(nv, opts);
                                         nested_row_values.push(nv_str);
                                     }
-                                    nested_rows.push(nested_row_values.join(","));
+                                    nested_rows.push(nested_row_values.join(&delim.to_string()));
                                 }
                             }
-                            format!("[{}]{{{}}}:{}", nested_count, nested_fields, nested_rows.join(";"))
+                            format!(
+                                "[{}]{{{}}}:{}",
+                                nested_count,
+                                nested_fields,
+                                nested_rows.join(&opts.row_separator.to_string())
+                            )
                         } else {
                             // Array of primitives: use bracket notation
-                            let items: Vec<String> = arr_val.iter().map(|v| value_to_toon(v, 0, 0)).collect();
-                            format!("[{}]", items.join(","))
+                            let items: Vec<String> = arr_val.iter().map(|v| value_to_toon(v, 0, opts)).collect();
+                            format!("[{}]", items.join(&delim.to_string()))
                         }
                     }
                     Value::Object(nested_obj) => {
                         // Nested object: use compact key:value format
                         let mut nested_items = Vec::new();
-                        let mut nested_keys: Vec<String> = nested_obj.keys().cloned().collect();
-                        nested_keys.sort();
+                        let nested_keys = collect_keys(nested_obj.keys(), opts.key_order);
                         for nk in nested_keys {
                             let nv = nested_obj.get(&nk).unwrap_or(&empty_value);
-                            let mut nv_str = value_to_toon(nv, 0, 0);
-                            if nv_str.contains(',') || nv_str.contains(':') {
-                                nv_str = format!("\"{}\"", nv_str);
-                            }
+                            let nv_str = row_cell_to_toon(nv, opts);
                             nested_items.push(format!("{}:{}", nk, nv_str));
                         }
-                        format!("{{{}}}", nested_items.join(","))
-                    }
-                    _ => {
-                        let mut value_str = value_to_toon(value, 0, 0);
-                        // Handle values with commas, newlines, colons, or semicolons
-                        // Only quote if not already quoted and contains special chars
-                        if !(value_str.starts_with('"') && value_str.ends_with('"')) {
-                            if value_str.contains(',') || value_str.contains('\n') || value_str.contains(':') || value_str.contains(';') {
-                                // Escape quotes if present
-                                if value_str.contains('"') {
-                                    value_str = value_str.replace('"', "\\\"");
-                                }
-                                value_str = format!("\"{}\"", value_str);
-                            }
-                        }
-                        value_str
+                        format!("{{{}}}", nested_items.join(&delim.to_string()))
                     }
+                    _ => row_cell_to_toon(value, opts),
                 };
                 row_values.push(value_str);
             }
-            let row = row_values.join(",");
+            let row = row_values.join(&delim.to_string());
             lines.push(format!("{}{}", data_prefix, row));
         }
     }
@@ -248,41 +306,44 @@ fn list_of_objects_to_toon(
     lines.join("\n")
 }
 
-fn value_to_toon(value: &Value, indent: usize, level: usize) -> String {
+/// Render a scalar/nested value for a tabular row, quoting it (and
+/// escaping any embedded quotes) if it would otherwise be ambiguous with
+/// the active delimiter, row separator, or `:`.
+fn row_cell_to_toon(value: &Value, opts: &ToonOptions) -> String {
+    let value_str = value_to_toon(value, 0, opts);
+    if value_str.starts_with('"') && value_str.ends_with('"') {
+        return value_str;
+    }
+    let delim = opts.delimiter.as_char();
+    if value_str.contains(delim)
+        || value_str.contains(opts.row_separator)
+        || value_str.contains('\n')
+        || value_str.contains(':')
+    {
+        let escaped = value_str.replace('"', "\\\"");
+        format!("\"{}\"", escaped)
+    } else {
+        value_str
+    }
+}
+
+fn value_to_toon(value: &Value, level: usize, opts: &ToonOptions) -> String {
     match value {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Number(n) => n.to_string(),
-        Value::String(s) => escape_string(s),
-        Value::Array(arr) => "\n".to_string() + &list_to_toon(arr, indent, level),
-        Value::Object(obj) => "\n".to_string() + &dict_to_toon(obj, indent, level),
+        Value::String(s) => escape_string_with_options(s, opts),
+        Value::Array(arr) => "\n".to_string() + &list_to_toon(arr, level, opts),
+        Value::Object(obj) => "\n".to_string() + &dict_to_toon(obj, level, opts),
     }
 }
 
-fn escape_string(s: &str) -> String {
-    // Only escape actual control characters (newlines, tabs, etc.)
-    // Let the caller decide if quoting is needed for other special chars
-    let has_control_chars = s.chars().any(|c| matches!(c, '\n' | '\t' | '\r'));
-
-    if !has_control_chars {
-        return s.to_string();
-    }
+pub(crate) fn escape_string(s: &str) -> String {
+    escape_string_with_options(s, &ToonOptions::default())
+}
 
-    // Escape control characters
-    let mut result = String::with_capacity(s.len() + 2);
-    result.push('"');
-    for c in s.chars() {
-        match c {
-            '\\' => result.push_str("\\\\"),
-            '"' => result.push_str("\\\""),
-            '\n' => result.push_str("\\n"),
-            '\r' => result.push_str("\\r"),
-            '\t' => result.push_str("\\t"),
-            _ => result.push(c),
-        }
-    }
-    result.push('"');
-    result
+fn escape_string_with_options(s: &str, opts: &ToonOptions) -> String {
+    options::quote_if_needed(s, opts)
 }
 
 #[cfg(test)]
@@ -411,5 +472,37 @@ mod tests {
         assert!(result.contains("users["));
         assert!(result.contains(",")); // Comma-separated values
     }
+
+    #[test]
+    fn test_key_order_sorted_tabular_fields() {
+        let data = json!([
+            {"name": "Alice", "age": 30},
+            {"name": "Bob", "age": 25}
+        ]);
+        let result = to_toon_with_key_order(&data, KeyOrder::Sorted);
+        assert!(result.contains("[2]{age,name}:"));
+    }
+
+    #[test]
+    fn test_key_order_as_encountered_tabular_fields() {
+        let data = json!([
+            {"name": "Alice", "age": 30},
+            {"name": "Bob", "age": 25}
+        ]);
+        let result = to_toon_with_key_order(&data, KeyOrder::AsEncountered);
+        assert!(result.contains("[2]{name,age}:"));
+    }
+
+    #[test]
+    fn test_key_order_union_preserves_first_seen_order() {
+        let data = json!([
+            {"name": "Alice", "age": 30},
+            {"email": "bob@example.com", "name": "Bob"}
+        ]);
+        let result = to_toon_with_key_order(&data, KeyOrder::AsEncountered);
+        // "email" is new in the second row but appended after the
+        // first row's keys, not sorted ahead of them.
+        assert!(result.contains("[2]{name,age,email}:"));
+    }
 }
 
@@ -0,0 +1,255 @@
+//! A configuration builder for delimiters, quoting, and indentation.
+//!
+//! [`ToonEncoder`] lets callers tune the encoder instead of living with the
+//! hardcoded behavior in [`to_toon_with_indent`](crate::to_toon_with_indent):
+//! a tab or pipe delimiter for tabular blocks (handy for squeezing out a
+//! few more tokens and sidestepping most quoting), a different nested-row
+//! separator, a quoting strategy, and indentation width/style.
+
+use crate::KeyOrder;
+use serde_json::Value;
+
+/// The field/row delimiter used inside a tabular block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delimiter {
+    #[default]
+    Comma,
+    Tab,
+    Pipe,
+}
+
+impl Delimiter {
+    pub(crate) fn as_char(self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+            Delimiter::Pipe => '|',
+        }
+    }
+}
+
+/// How aggressively string values get wrapped in quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStrategy {
+    /// Quote only when a value contains a control character (the
+    /// original, default behavior).
+    #[default]
+    Minimal,
+    /// Always wrap string values in quotes.
+    Always,
+    /// Quote when a value contains a control character or would be
+    /// ambiguous with the active delimiter, row separator, or `:`.
+    OnDelimiterConflict,
+}
+
+/// Encoder configuration: indentation, tabular delimiter/row separator,
+/// quoting strategy, and key ordering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToonOptions {
+    pub indent_width: usize,
+    pub use_tabs: bool,
+    pub delimiter: Delimiter,
+    pub row_separator: char,
+    pub quote_strategy: QuoteStrategy,
+    pub key_order: KeyOrder,
+}
+
+impl Default for ToonOptions {
+    fn default() -> Self {
+        ToonOptions {
+            indent_width: 2,
+            use_tabs: false,
+            delimiter: Delimiter::default(),
+            row_separator: ';',
+            quote_strategy: QuoteStrategy::default(),
+            key_order: KeyOrder::default(),
+        }
+    }
+}
+
+impl ToonOptions {
+    pub(crate) fn prefix(&self, level: usize) -> String {
+        if self.use_tabs {
+            "\t".repeat(level)
+        } else {
+            " ".repeat(self.indent_width * level)
+        }
+    }
+
+    pub(crate) fn needs_quoting(&self, s: &str) -> bool {
+        let has_control_chars = s.chars().any(|c| matches!(c, '\n' | '\t' | '\r'));
+        match self.quote_strategy {
+            QuoteStrategy::Always => true,
+            QuoteStrategy::Minimal => has_control_chars,
+            QuoteStrategy::OnDelimiterConflict => {
+                has_control_chars
+                    || s.contains(self.delimiter.as_char())
+                    || s.contains(self.row_separator)
+                    || s.contains(':')
+            }
+        }
+    }
+}
+
+/// Quote and escape `s` if `opts` decides it needs it; otherwise return it
+/// unchanged. This is what [`escape_string`](crate::escape_string) delegates
+/// to with the default options.
+pub(crate) fn quote_if_needed(s: &str, opts: &ToonOptions) -> String {
+    if !opts.needs_quoting(s) {
+        return s.to_string();
+    }
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// A configurable TOON encoder.
+///
+/// # Examples
+///
+/// ```
+/// use totoon::{ToonEncoder, Delimiter};
+/// use serde_json::json;
+///
+/// let data = json!([
+///     {"name": "Alice", "age": 30},
+///     {"name": "Bob", "age": 25}
+/// ]);
+///
+/// let toon = ToonEncoder::new().delimiter(Delimiter::Tab).encode(&data);
+/// assert!(toon.contains("Alice\t30"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ToonEncoder {
+    options: ToonOptions,
+}
+
+impl ToonEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn indent_width(mut self, width: usize) -> Self {
+        self.options.indent_width = width;
+        self
+    }
+
+    pub fn use_tabs(mut self, use_tabs: bool) -> Self {
+        self.options.use_tabs = use_tabs;
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: Delimiter) -> Self {
+        self.options.delimiter = delimiter;
+        self
+    }
+
+    pub fn row_separator(mut self, row_separator: char) -> Self {
+        self.options.row_separator = row_separator;
+        self
+    }
+
+    pub fn quote_strategy(mut self, quote_strategy: QuoteStrategy) -> Self {
+        self.options.quote_strategy = quote_strategy;
+        self
+    }
+
+    pub fn key_order(mut self, key_order: KeyOrder) -> Self {
+        self.options.key_order = key_order;
+        self
+    }
+
+    pub fn encode(&self, value: &Value) -> String {
+        crate::render_with_options(value, 0, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_encoder_matches_to_toon() {
+        let data = json!({"name": "Alice", "age": 30});
+        assert_eq!(ToonEncoder::new().encode(&data), crate::to_toon(&data));
+    }
+
+    #[test]
+    fn test_tab_delimiter() {
+        let data = json!([
+            {"name": "Alice", "age": 30},
+            {"name": "Bob", "age": 25}
+        ]);
+        let toon = ToonEncoder::new().delimiter(Delimiter::Tab).encode(&data);
+        assert!(toon.contains("Alice\t30"));
+        assert!(toon.contains("Bob\t25"));
+    }
+
+    #[test]
+    fn test_pipe_delimiter() {
+        let data = json!([
+            {"name": "Alice", "age": 30},
+            {"name": "Bob", "age": 25}
+        ]);
+        let toon = ToonEncoder::new().delimiter(Delimiter::Pipe).encode(&data);
+        assert!(toon.contains("Alice|30"));
+    }
+
+    #[test]
+    fn test_always_quote_strings() {
+        let data = json!({"name": "Alice"});
+        let toon = ToonEncoder::new()
+            .quote_strategy(QuoteStrategy::Always)
+            .encode(&data);
+        assert!(toon.contains("name: \"Alice\""));
+    }
+
+    #[test]
+    fn test_delimiter_conflict_quoting() {
+        let data = json!([
+            {"name": "Alice, Jr.", "age": 30}
+        ]);
+        let toon = ToonEncoder::new()
+            .quote_strategy(QuoteStrategy::OnDelimiterConflict)
+            .encode(&data);
+        assert!(toon.contains("\"Alice, Jr.\""));
+    }
+
+    #[test]
+    fn test_custom_row_separator() {
+        let data = json!({
+            "users": [
+                {"name": "Alice", "tags": [{"id": 1}, {"id": 2}]}
+            ]
+        });
+        let toon = ToonEncoder::new().row_separator('|').encode(&data);
+        assert!(!toon.contains("1;2"));
+        assert!(toon.contains('|'));
+    }
+
+    #[test]
+    fn test_tab_indentation() {
+        let data = json!({"user": {"name": "Alice"}});
+        let toon = ToonEncoder::new().use_tabs(true).encode(&data);
+        assert!(toon.contains("\tname: Alice"));
+    }
+
+    #[test]
+    fn test_key_order_through_encoder() {
+        let data = json!([{"name": "Alice", "age": 30}]);
+        let toon = ToonEncoder::new().key_order(KeyOrder::Sorted).encode(&data);
+        assert!(toon.contains("[1]{age,name}:"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Field projection: prune a `Value` down to a chosen subset of fields
+//! before it is handed to the encoder, so callers can drop columns that
+//! don't matter for a given prompt.
+
+use serde_json::{Map, Value};
+
+/// Convert `value` to TOON, keeping only the fields named by `selectors`.
+///
+/// Selectors are dot-paths (e.g. `"users.name"`). A selector matches a key
+/// when `selector.starts_with(key)` and the character right after `key` in
+/// the selector is `.` or end-of-string: `"users"` keeps the whole `users`
+/// subtree, while `"users.name"` descends into each object of the `users`
+/// array and keeps only `name`. An empty selector list keeps everything.
+///
+/// # Examples
+///
+/// ```
+/// use totoon::to_toon_selective;
+/// use serde_json::json;
+///
+/// let data = json!({
+///     "users": [
+///         {"name": "Alice", "age": 30, "email": "alice@example.com"},
+///         {"name": "Bob", "age": 25, "email": "bob@example.com"}
+///     ]
+/// });
+///
+/// let toon = to_toon_selective(&data, &["users.name"]);
+/// assert!(toon.contains("name"));
+/// assert!(!toon.contains("email"));
+/// ```
+pub fn to_toon_selective(value: &Value, selectors: &[&str]) -> String {
+    if selectors.is_empty() {
+        return crate::to_toon(value);
+    }
+    let selectors: Vec<String> = selectors.iter().map(|s| s.to_string()).collect();
+    crate::to_toon(&prune_value(value, &selectors))
+}
+
+enum KeyMatch {
+    None,
+    Whole,
+    Descend(Vec<String>),
+}
+
+fn match_key(selectors: &[String], key: &str) -> KeyMatch {
+    let mut children = Vec::new();
+    for selector in selectors {
+        if selector == key {
+            return KeyMatch::Whole;
+        }
+        if let Some(rest) = selector.strip_prefix(key) {
+            if let Some(child) = rest.strip_prefix('.') {
+                children.push(child.to_string());
+            }
+        }
+    }
+    if children.is_empty() {
+        KeyMatch::None
+    } else {
+        KeyMatch::Descend(children)
+    }
+}
+
+fn prune_value(value: &Value, selectors: &[String]) -> Value {
+    match value {
+        Value::Object(obj) => Value::Object(prune_object(obj, selectors)),
+        Value::Array(arr) if !arr.is_empty() && arr.iter().all(|v| v.is_object()) => {
+            Value::Array(arr.iter().map(|v| prune_value(v, selectors)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+fn prune_object(obj: &Map<String, Value>, selectors: &[String]) -> Map<String, Value> {
+    let mut pruned = Map::new();
+    for (key, value) in obj {
+        match match_key(selectors, key) {
+            KeyMatch::None => {}
+            KeyMatch::Whole => {
+                pruned.insert(key.clone(), value.clone());
+            }
+            KeyMatch::Descend(child_selectors) => {
+                pruned.insert(key.clone(), prune_value(value, &child_selectors));
+            }
+        }
+    }
+    pruned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_toon;
+    use serde_json::json;
+
+    #[test]
+    fn test_empty_selectors_keeps_everything() {
+        let data = json!({"name": "Alice", "age": 30});
+        assert_eq!(to_toon_selective(&data, &[]), to_toon(&data));
+    }
+
+    #[test]
+    fn test_top_level_field_selection() {
+        let data = json!({"name": "Alice", "age": 30});
+        let result = to_toon_selective(&data, &["name"]);
+        assert!(result.contains("name: Alice"));
+        assert!(!result.contains("age"));
+    }
+
+    #[test]
+    fn test_whole_subtree_selection() {
+        let data = json!({
+            "user": {"name": "Alice", "age": 30},
+            "meta": {"count": 1}
+        });
+        let result = to_toon_selective(&data, &["user"]);
+        assert!(result.contains("name: Alice"));
+        assert!(result.contains("age: 30"));
+        assert!(!result.contains("meta"));
+    }
+
+    #[test]
+    fn test_dot_path_descends_into_array_of_objects() {
+        let data = json!({
+            "users": [
+                {"name": "Alice", "age": 30, "email": "a@example.com"},
+                {"name": "Bob", "age": 25, "email": "b@example.com"}
+            ]
+        });
+        let result = to_toon_selective(&data, &["users.name"]);
+        assert!(result.contains("name"));
+        assert!(result.contains("Alice"));
+        assert!(result.contains("Bob"));
+        assert!(!result.contains("age"));
+        assert!(!result.contains("email"));
+    }
+
+    #[test]
+    fn test_multiple_selectors() {
+        let data = json!({
+            "users": [{"name": "Alice", "age": 30, "email": "a@example.com"}],
+            "count": 1
+        });
+        let result = to_toon_selective(&data, &["users.name", "count"]);
+        assert!(result.contains("Alice"));
+        assert!(result.contains("count: 1"));
+        assert!(!result.contains("age"));
+        assert!(!result.contains("email"));
+    }
+
+    #[test]
+    fn test_prefix_without_separator_does_not_match() {
+        let data = json!({"user": "Alice", "username": "bob"});
+        let result = to_toon_selective(&data, &["user"]);
+        assert!(result.contains("user: Alice"));
+        assert!(!result.contains("username"));
+    }
+}
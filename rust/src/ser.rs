@@ -0,0 +1,736 @@
+//! A native `serde::Serializer` that emits TOON text directly from any
+//! `T: Serialize`, without first allocating a `serde_json::Value`.
+//!
+//! The compound serializers (`SerializeSeq`, `SerializeMap`, ...) buffer
+//! their elements into a small intermediate [`SerValue`] tree just long
+//! enough to detect the "sequence of maps with consistent keys" shape, so
+//! it can be rendered as the compact `[N]{fields}:` tabular form instead of
+//! a dash list - matching `list_of_objects_to_toon`. Rendering then reuses
+//! the same indentation and `escape_string` rules as the rest of the crate.
+
+use crate::escape_string;
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use std::fmt;
+
+/// Error produced while serializing a value to TOON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TOON serialization error: {}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Intermediate tree built up while serializing, just rich enough to
+/// render TOON the same way `to_toon_with_indent` renders a `Value`.
+/// One difference: a sequence of maps nested directly inside a dash list
+/// is written in tabular form, where the `Value` encoder writes a dash list.
+pub enum SerValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Seq(Vec<SerValue>),
+    Map(Vec<(String, SerValue)>),
+}
+
+/// Serialize any `T: Serialize` directly to a TOON string.
+///
+/// # Examples
+///
+/// ```
+/// use totoon::to_toon_string;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let toon = to_toon_string(&Person { name: "Alice".to_string(), age: 30 }).unwrap();
+/// assert!(toon.contains("name: Alice"));
+/// assert!(toon.contains("age: 30"));
+/// ```
+pub fn to_toon_string<T: Serialize>(value: &T) -> Result<String, Error> {
+    let ser_value = value.serialize(Serializer)?;
+    Ok(render(&ser_value, 2, 0))
+}
+
+fn render(value: &SerValue, indent: usize, level: usize) -> String {
+    match value {
+        SerValue::Null => "null".to_string(),
+        SerValue::Bool(b) => b.to_string(),
+        SerValue::Int(i) => i.to_string(),
+        SerValue::UInt(u) => u.to_string(),
+        SerValue::Float(f) => serde_json::Number::from_f64(*f)
+            .map_or_else(|| "null".to_string(), |n| n.to_string()),
+        SerValue::String(s) => escape_string(s),
+        SerValue::Seq(items) => render_seq(items, indent, level),
+        SerValue::Map(fields) => render_map(fields, indent, level),
+    }
+}
+
+fn render_seq(items: &[SerValue], indent: usize, level: usize) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    if items.iter().all(|v| matches!(v, SerValue::Map(_))) {
+        return render_map_seq("", items, indent, level);
+    }
+
+    let prefix = " ".repeat(indent * level);
+    items
+        .iter()
+        .map(|item| format!("{}- {}", prefix, render_inline(item, indent, level)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Like `render`, but nested arrays/objects get a leading newline so they
+/// start on their own indented lines, matching `value_to_toon`.
+fn render_inline(value: &SerValue, indent: usize, level: usize) -> String {
+    match value {
+        SerValue::Seq(items) => "\n".to_string() + &render_seq(items, indent, level),
+        SerValue::Map(fields) => "\n".to_string() + &render_map(fields, indent, level),
+        other => render(other, indent, level),
+    }
+}
+
+fn render_map(fields: &[(String, SerValue)], indent: usize, level: usize) -> String {
+    if fields.is_empty() {
+        return "{}".to_string();
+    }
+
+    let prefix = " ".repeat(indent * level);
+    let mut lines = Vec::new();
+    for (key, value) in fields {
+        match value {
+            SerValue::Map(inner) if !inner.is_empty() => {
+                lines.push(format!("{}{}:", prefix, key));
+                lines.push(render_map(inner, indent, level + 1));
+            }
+            SerValue::Seq(items) if !items.is_empty() => {
+                if items.iter().all(|v| matches!(v, SerValue::Map(_))) {
+                    lines.push(render_map_seq(key, items, indent, level));
+                } else {
+                    lines.push(format!("{}{}:", prefix, key));
+                    lines.push(render_seq(items, indent, level + 1));
+                }
+            }
+            _ => {
+                lines.push(format!("{}{}: {}", prefix, key, render_inline(value, indent, level + 1)));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Render a sequence of maps as the compact `key[N]{f1,f2}:` tabular form,
+/// gathering the union of keys in first-seen order across every element.
+fn render_map_seq(key: &str, items: &[SerValue], indent: usize, level: usize) -> String {
+    let mut all_keys: Vec<String> = Vec::new();
+    for item in items {
+        if let SerValue::Map(fields) = item {
+            for (k, _) in fields {
+                if !all_keys.contains(k) {
+                    all_keys.push(k.clone());
+                }
+            }
+        }
+    }
+    if all_keys.is_empty() {
+        return "[]".to_string();
+    }
+
+    let prefix = " ".repeat(indent * level);
+    let header_fields = all_keys.join(",");
+    let count = items.len();
+    let mut lines = Vec::new();
+    if key.is_empty() {
+        lines.push(format!("{}[{}]{{{}}}:", prefix, count, header_fields));
+    } else {
+        lines.push(format!("{}{}[{}]{{{}}}:", prefix, key, count, header_fields));
+    }
+
+    // Data rows sit one level deeper than the header, matching the
+    // `Value` encoder in `lib.rs`.
+    let data_prefix = " ".repeat(indent * (level + 1));
+    for item in items {
+        let fields = match item {
+            SerValue::Map(fields) => fields,
+            _ => continue,
+        };
+        let row = all_keys
+            .iter()
+            .map(|k| {
+                let value = fields.iter().find(|(fk, _)| fk == k).map(|(_, v)| v);
+                render_row_cell(value)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        lines.push(format!("{}{}", data_prefix, row));
+    }
+    lines.join("\n")
+}
+
+fn render_row_cell(value: Option<&SerValue>) -> String {
+    let Some(value) = value else {
+        return String::new();
+    };
+    match value {
+        SerValue::Seq(items) if items.iter().all(|v| matches!(v, SerValue::Map(_))) => {
+            // Compact inline tabular form: `[N]{fields}:row1;row2`.
+            let block = render_map_seq("", items, 0, 0);
+            let mut lines = block.lines();
+            let header = lines.next().unwrap_or_default().to_string();
+            header + &lines.collect::<Vec<_>>().join(";")
+        }
+        SerValue::Seq(items) => {
+            let cells: Vec<String> = items.iter().map(|v| render(v, 0, 0)).collect();
+            format!("[{}]", cells.join(","))
+        }
+        SerValue::Map(fields) => {
+            let cells: Vec<String> =
+                fields.iter().map(|(k, v)| format!("{}:{}", k, render_row_cell(Some(v)))).collect();
+            format!("{{{}}}", cells.join(","))
+        }
+        _ => {
+            let mut s = render(value, 0, 0);
+            if !(s.starts_with('"') && s.ends_with('"'))
+                && (s.contains(',') || s.contains('\n') || s.contains(':') || s.contains(';'))
+            {
+                s = format!("\"{}\"", s.replace('"', "\\\""));
+            }
+            s
+        }
+    }
+}
+
+/// The entry point: a zero-sized `Serializer` that turns any `Serialize`
+/// value into a [`SerValue`].
+#[derive(Clone, Copy)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = TupleVariantSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = StructVariantSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::UInt(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(v as f64)
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::String(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::String(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Seq(v.iter().map(|b| SerValue::UInt(*b as u64)).collect()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Null)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Null)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let inner = value.serialize(self)?;
+        Ok(SerValue::Map(vec![(variant.to_string(), inner)]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(TupleVariantSerializer {
+            variant,
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MapSerializer {
+            fields: Vec::with_capacity(len.unwrap_or(0)),
+            pending_key: None,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(StructSerializer {
+            fields: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(StructVariantSerializer {
+            variant,
+            fields: Vec::with_capacity(len),
+        })
+    }
+}
+
+pub struct SeqSerializer {
+    items: Vec<SerValue>,
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Seq(self.items))
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+pub struct TupleVariantSerializer {
+    variant: &'static str,
+    items: Vec<SerValue>,
+}
+
+impl SerializeTupleVariant for TupleVariantSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Map(vec![(self.variant.to_string(), SerValue::Seq(self.items))]))
+    }
+}
+
+pub struct MapSerializer {
+    fields: Vec<(String, SerValue)>,
+    pending_key: Option<String>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.pending_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| Error("serialize_value called before serialize_key".to_string()))?;
+        self.fields.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Map(self.fields))
+    }
+}
+
+pub struct StructSerializer {
+    fields: Vec<(String, SerValue)>,
+}
+
+impl SerializeStruct for StructSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.fields.push((key.to_string(), value.serialize(Serializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Map(self.fields))
+    }
+}
+
+pub struct StructVariantSerializer {
+    variant: &'static str,
+    fields: Vec<(String, SerValue)>,
+}
+
+impl SerializeStructVariant for StructVariantSerializer {
+    type Ok = SerValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.fields.push((key.to_string(), value.serialize(Serializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(SerValue::Map(vec![(self.variant.to_string(), SerValue::Map(self.fields))]))
+    }
+}
+
+/// Serializes map/struct keys (which must be string-like) to a `String`.
+struct MapKeySerializer;
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = ser::Impossible<String, Error>;
+    type SerializeStruct = ser::Impossible<String, Error>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_string())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error("map keys must be string-like".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    #[test]
+    fn test_struct_to_toon_string() {
+        let person = Person {
+            name: "Alice".to_string(),
+            age: 30,
+        };
+        let result = to_toon_string(&person).unwrap();
+        assert!(result.contains("name: Alice"));
+        assert!(result.contains("age: 30"));
+    }
+
+    #[test]
+    fn test_sequence_of_structs_uses_tabular_form() {
+        let people = vec![
+            Person { name: "Alice".to_string(), age: 30 },
+            Person { name: "Bob".to_string(), age: 25 },
+        ];
+        let result = to_toon_string(&people).unwrap();
+        assert!(result.contains("[2]{name,age}:"));
+        assert!(result.contains("Alice,30"));
+        assert!(result.contains("Bob,25"));
+    }
+
+    #[test]
+    fn test_primitive_sequence_uses_dash_list() {
+        let values: Vec<i32> = vec![1, 2, 3];
+        let result = to_toon_string(&values).unwrap();
+        assert!(result.contains("- 1"));
+        assert!(result.contains("- 2"));
+        assert!(result.contains("- 3"));
+    }
+
+    #[test]
+    fn test_map_serialization() {
+        let mut map = BTreeMap::new();
+        map.insert("name", "Alice");
+        map.insert("age", "30");
+        let result = to_toon_string(&map).unwrap();
+        assert!(result.contains("name: Alice"));
+        assert!(result.contains("age: 30"));
+    }
+
+    #[test]
+    fn test_nested_sequence_of_structs_indents_rows_one_level_deeper() {
+        #[derive(Serialize)]
+        struct Group {
+            people: Vec<Person>,
+        }
+        let group = Group {
+            people: vec![
+                Person { name: "Alice".to_string(), age: 30 },
+                Person { name: "Bob".to_string(), age: 25 },
+            ],
+        };
+        let result = to_toon_string(&group).unwrap();
+        assert_eq!(
+            result,
+            crate::to_toon(&serde_json::json!({
+                "people": [
+                    {"name": "Alice", "age": 30},
+                    {"name": "Bob", "age": 25}
+                ]
+            }))
+        );
+        assert!(crate::from_toon(&result).is_ok());
+    }
+
+    #[test]
+    fn test_option_and_primitives() {
+        assert_eq!(to_toon_string(&42i32).unwrap(), "42");
+        assert_eq!(to_toon_string(&true).unwrap(), "true");
+        assert_eq!(to_toon_string(&"hello").unwrap(), "hello");
+        let none: Option<i32> = None;
+        assert_eq!(to_toon_string(&none).unwrap(), "null");
+    }
+}